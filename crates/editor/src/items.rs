@@ -1,12 +1,13 @@
 use crate::{Autoscroll, Editor, Event, MultiBuffer, NavigationData, ToOffset, ToPoint as _};
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use gpui::{
-    elements::*, AppContext, Entity, ModelContext, ModelHandle, RenderContext, Subscription, Task,
-    View, ViewContext, ViewHandle, WeakModelHandle,
+    actions, elements::*, AppContext, CursorStyle, Entity, ModelContext, ModelHandle, MouseButton,
+    RenderContext, Subscription, Task, View, ViewContext, ViewHandle, WeakModelHandle,
 };
-use language::{Bias, Buffer, Diagnostic, File as _};
+use language::{Bias, Buffer, Diagnostic, DiagnosticSeverity, File as _};
 use project::{File, Project, ProjectPath};
 use std::fmt::Write;
+use std::ops::Range;
 use std::path::PathBuf;
 use text::{Point, Selection};
 use util::ResultExt;
@@ -108,27 +109,10 @@ impl ItemView for Editor {
         project: ModelHandle<Project>,
         cx: &mut ViewContext<Self>,
     ) -> Task<Result<()>> {
-        let buffer = self.buffer().clone();
-        let buffers = buffer.read(cx).all_buffers();
-        let transaction = project.update(cx, |project, cx| project.format(buffers, true, cx));
-        cx.spawn(|this, mut cx| async move {
-            let transaction = transaction.await.log_err();
-            this.update(&mut cx, |editor, cx| {
-                editor.request_autoscroll(Autoscroll::Fit, cx)
-            });
-            buffer
-                .update(&mut cx, |buffer, cx| {
-                    if let Some(transaction) = transaction {
-                        if !buffer.is_singleton() {
-                            buffer.push_transaction(&transaction.0);
-                        }
-                    }
-
-                    buffer.save(cx)
-                })
-                .await?;
-            Ok(())
-        })
+        let format_on_save = cx
+            .app_state::<Settings>()
+            .format_on_save(self.language_name(cx).as_deref());
+        self.save_internal(project, format_on_save, cx)
     }
 
     fn can_save_as(&self, cx: &AppContext) -> bool {
@@ -141,15 +125,37 @@ impl ItemView for Editor {
         abs_path: PathBuf,
         cx: &mut ViewContext<Self>,
     ) -> Task<Result<()>> {
-        let buffer = self
-            .buffer()
-            .read(cx)
-            .as_singleton()
-            .expect("cannot call save_as on an excerpt list")
-            .clone();
-
-        project.update(cx, |project, cx| {
-            project.save_buffer_as(buffer, abs_path, cx)
+        let multi_buffer = self.buffer().clone();
+        if let Some(buffer) = multi_buffer.read(cx).as_singleton() {
+            return project.update(cx, |project, cx| {
+                project.save_buffer_as(buffer, abs_path, cx)
+            });
+        }
+
+        let excerpts = multi_buffer.read(cx).snapshot(cx).excerpt_boundaries();
+        let text = crate::multi_buffer::render_excerpts(&excerpts);
+
+        let window_id = cx.window_id();
+        let workspace = self.workspace.clone();
+        cx.spawn(|_, mut cx| async move {
+            let project_path = project
+                .update(&mut cx, |project, cx| {
+                    project.save_text_as(abs_path, text, cx)
+                })
+                .await?;
+
+            let opened = project
+                .update(&mut cx, |project, cx| {
+                    BufferOpener.open(project, project_path, window_id, cx)
+                })
+                .ok_or_else(|| anyhow!("failed to open saved excerpt export"))?
+                .await?;
+
+            if let Some(workspace) = workspace {
+                workspace.update(&mut cx, |workspace, cx| workspace.open_item(opened, cx));
+            }
+
+            Ok(())
         })
     }
 
@@ -166,9 +172,72 @@ impl ItemView for Editor {
     }
 }
 
+actions!(editor, [SaveWithoutFormat]);
+
+impl Editor {
+    fn save_internal(
+        &mut self,
+        project: ModelHandle<Project>,
+        format: bool,
+        cx: &mut ViewContext<Self>,
+    ) -> Task<Result<()>> {
+        let buffer = self.buffer().clone();
+        let transaction = if format {
+            let buffers = buffer.read(cx).all_buffers();
+            Some(project.update(cx, |project, cx| project.format(buffers, true, cx)))
+        } else {
+            None
+        };
+        cx.spawn(|this, mut cx| async move {
+            let transaction = match transaction {
+                Some(transaction) => transaction.await.log_err(),
+                None => None,
+            };
+            this.update(&mut cx, |editor, cx| {
+                editor.request_autoscroll(Autoscroll::Fit, cx)
+            });
+            buffer
+                .update(&mut cx, |buffer, cx| {
+                    if let Some(transaction) = transaction {
+                        if !buffer.is_singleton() {
+                            buffer.push_transaction(&transaction.0);
+                        }
+                    }
+
+                    buffer.save(cx)
+                })
+                .await?;
+            Ok(())
+        })
+    }
+
+    pub fn save_without_formatting(&mut self, _: &SaveWithoutFormat, cx: &mut ViewContext<Self>) {
+        if let Some(project) = self.project.clone() {
+            let save = self.save_internal(project, false, cx);
+            cx.spawn(|_, _| async move {
+                save.await.log_err();
+            })
+            .detach();
+        }
+    }
+}
+
+/// Normalizes a `Point` range's end row so a selection whose end lands at
+/// column 0 of the next row (a line-wise Shift+Down or triple-click
+/// selection) doesn't count that empty trailing row as included.
+pub(crate) fn normalize_end_row(start_row: u32, end_point: Point) -> u32 {
+    if end_point.column == 0 && end_point.row > start_row {
+        end_point.row - 1
+    } else {
+        end_point.row
+    }
+}
+
 pub struct CursorPosition {
     position: Option<Point>,
     selected_count: usize,
+    selected_line_count: usize,
+    selection_count: usize,
     _observe_active_editor: Option<Subscription>,
 }
 
@@ -177,6 +246,8 @@ impl CursorPosition {
         Self {
             position: None,
             selected_count: 0,
+            selected_line_count: 0,
+            selection_count: 0,
             _observe_active_editor: None,
         }
     }
@@ -186,9 +257,18 @@ impl CursorPosition {
         let buffer = editor.buffer().read(cx).snapshot(cx);
 
         self.selected_count = 0;
+        self.selected_line_count = 0;
+        self.selection_count = 0;
         let mut last_selection: Option<Selection<usize>> = None;
         for selection in editor.local_selections::<usize>(cx) {
+            self.selection_count += 1;
             self.selected_count += selection.end - selection.start;
+            if selection.end > selection.start {
+                let start_row = selection.start.to_point(&buffer).row;
+                let end_point = selection.end.to_point(&buffer);
+                let end_row = normalize_end_row(start_row, end_point);
+                self.selected_line_count += (end_row - start_row + 1) as usize;
+            }
             if last_selection
                 .as_ref()
                 .map_or(true, |last_selection| selection.id > last_selection.id)
@@ -213,9 +293,28 @@ impl View for CursorPosition {
 
     fn render(&mut self, cx: &mut RenderContext<Self>) -> ElementBox {
         if let Some(position) = self.position {
-            let theme = &cx.app_state::<Settings>().theme.workspace.status_bar;
+            let settings = cx.app_state::<Settings>();
+            let theme = &settings.theme.workspace.status_bar;
             let mut text = format!("{},{}", position.row + 1, position.column + 1);
-            if self.selected_count > 0 {
+            if settings.cursor_position_detailed() {
+                if self.selection_count > 1 || self.selected_count > 0 {
+                    write!(
+                        text,
+                        "  ({} selection{}, {} char{}, {} line{})",
+                        self.selection_count,
+                        if self.selection_count == 1 { "" } else { "s" },
+                        self.selected_count,
+                        if self.selected_count == 1 { "" } else { "s" },
+                        self.selected_line_count,
+                        if self.selected_line_count == 1 {
+                            ""
+                        } else {
+                            "s"
+                        },
+                    )
+                    .unwrap();
+                }
+            } else if self.selected_count > 0 {
                 write!(text, " ({} selected)", self.selected_count).unwrap();
             }
             Label::new(text, theme.cursor_position.clone()).boxed()
@@ -243,8 +342,84 @@ impl StatusItemView for CursorPosition {
     }
 }
 
+actions!(editor, [NextDiagnostic, PrevDiagnostic]);
+
+/// Finds the diagnostic range to jump to when moving forward from `cursor`,
+/// wrapping around to the first diagnostic in the buffer if none follow it.
+pub(crate) fn next_diagnostic_range(
+    ranges: impl Iterator<Item = Range<usize>>,
+    cursor: usize,
+) -> Option<Range<usize>> {
+    let mut after: Option<Range<usize>> = None;
+    let mut wrapped: Option<Range<usize>> = None;
+    for range in ranges {
+        if range.is_empty() {
+            continue;
+        }
+        if range.start > cursor {
+            if after.as_ref().map_or(true, |a| range.start < a.start) {
+                after = Some(range.clone());
+            }
+        } else if wrapped.as_ref().map_or(true, |w| range.start < w.start) {
+            wrapped = Some(range);
+        }
+    }
+    after.or(wrapped)
+}
+
+/// Finds the diagnostic range to jump to when moving backward from `cursor`,
+/// wrapping around to the last diagnostic in the buffer if none precede it.
+pub(crate) fn prev_diagnostic_range(
+    ranges: impl Iterator<Item = Range<usize>>,
+    cursor: usize,
+) -> Option<Range<usize>> {
+    let mut before: Option<Range<usize>> = None;
+    let mut wrapped: Option<Range<usize>> = None;
+    for range in ranges {
+        if range.is_empty() {
+            continue;
+        }
+        if range.end < cursor {
+            if before.as_ref().map_or(true, |b| range.start > b.start) {
+                before = Some(range.clone());
+            }
+        } else if wrapped.as_ref().map_or(true, |w| range.start > w.start) {
+            wrapped = Some(range);
+        }
+    }
+    before.or(wrapped)
+}
+
+impl Editor {
+    pub fn next_diagnostic(&mut self, _: &NextDiagnostic, cx: &mut ViewContext<Self>) {
+        self.go_to_diagnostic(true, cx);
+    }
+
+    pub fn prev_diagnostic(&mut self, _: &PrevDiagnostic, cx: &mut ViewContext<Self>) {
+        self.go_to_diagnostic(false, cx);
+    }
+
+    fn go_to_diagnostic(&mut self, forward: bool, cx: &mut ViewContext<Self>) {
+        let buffer = self.buffer().read(cx).snapshot(cx);
+        let cursor = self.newest_selection_with_snapshot::<usize>(&buffer).head();
+        let ranges = buffer
+            .diagnostics_in_range::<_, usize>(0..buffer.len(), false)
+            .map(|entry| entry.range);
+        let target = if forward {
+            next_diagnostic_range(ranges, cursor)
+        } else {
+            prev_diagnostic_range(ranges, cursor)
+        };
+        if let Some(range) = target {
+            self.select_ranges([range], Some(Autoscroll::Fit), cx);
+        }
+    }
+}
+
 pub struct DiagnosticMessage {
     diagnostic: Option<Diagnostic>,
+    error_count: usize,
+    warning_count: usize,
     _observe_active_editor: Option<Subscription>,
 }
 
@@ -252,6 +427,8 @@ impl DiagnosticMessage {
     pub fn new() -> Self {
         Self {
             diagnostic: None,
+            error_count: 0,
+            warning_count: 0,
             _observe_active_editor: None,
         }
     }
@@ -262,14 +439,30 @@ impl DiagnosticMessage {
         let cursor_position = editor
             .newest_selection_with_snapshot::<usize>(&buffer.read(cx))
             .head();
-        let new_diagnostic = buffer
-            .read(cx)
+        let snapshot = buffer.read(cx);
+        let new_diagnostic = snapshot
             .diagnostics_in_range::<_, usize>(cursor_position..cursor_position, false)
             .filter(|entry| !entry.range.is_empty())
             .min_by_key(|entry| (entry.diagnostic.severity, entry.range.len()))
             .map(|entry| entry.diagnostic);
-        if new_diagnostic != self.diagnostic {
+
+        let mut error_count = 0;
+        let mut warning_count = 0;
+        for entry in snapshot.diagnostics_in_range::<_, usize>(0..snapshot.len(), false) {
+            match entry.diagnostic.severity {
+                DiagnosticSeverity::ERROR => error_count += 1,
+                DiagnosticSeverity::WARNING => warning_count += 1,
+                _ => {}
+            }
+        }
+
+        if new_diagnostic != self.diagnostic
+            || error_count != self.error_count
+            || warning_count != self.warning_count
+        {
             self.diagnostic = new_diagnostic;
+            self.error_count = error_count;
+            self.warning_count = warning_count;
             cx.notify();
         }
     }
@@ -285,16 +478,45 @@ impl View for DiagnosticMessage {
     }
 
     fn render(&mut self, cx: &mut RenderContext<Self>) -> ElementBox {
-        if let Some(diagnostic) = &self.diagnostic {
-            let theme = &cx.app_state::<Settings>().theme.workspace.status_bar;
-            Label::new(
-                diagnostic.message.split('\n').next().unwrap().to_string(),
-                theme.diagnostic_message.clone(),
-            )
-            .boxed()
+        let theme = &cx.app_state::<Settings>().theme.workspace.status_bar;
+        let message = if let Some(diagnostic) = &self.diagnostic {
+            diagnostic.message.split('\n').next().unwrap().to_string()
+        } else if self.error_count > 0 || self.warning_count > 0 {
+            let mut message = String::new();
+            if self.error_count > 0 {
+                write!(
+                    message,
+                    "{} error{}",
+                    self.error_count,
+                    if self.error_count == 1 { "" } else { "s" }
+                )
+                .unwrap();
+            }
+            if self.warning_count > 0 {
+                if !message.is_empty() {
+                    message.push_str(", ");
+                }
+                write!(
+                    message,
+                    "{} warning{}",
+                    self.warning_count,
+                    if self.warning_count == 1 { "" } else { "s" }
+                )
+                .unwrap();
+            }
+            message
         } else {
-            Empty::new().boxed()
-        }
+            return Empty::new().boxed();
+        };
+
+        MouseEventHandler::new::<Self, _, _>(0, cx, |_, _| {
+            Label::new(message, theme.diagnostic_message.clone()).boxed()
+        })
+        .with_cursor_style(CursorStyle::PointingHand)
+        .on_click(MouseButton::Left, |_, cx| {
+            cx.dispatch_action(NextDiagnostic);
+        })
+        .boxed()
     }
 }
 
@@ -314,3 +536,50 @@ impl StatusItemView for DiagnosticMessage {
         cx.notify();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_end_row_excludes_empty_trailing_row() {
+        // A line-wise selection from row 2 to column 0 of row 5 only
+        // actually covers rows 2-4; row 5 has nothing selected on it.
+        assert_eq!(normalize_end_row(2, Point::new(5, 0)), 4);
+    }
+
+    #[test]
+    fn normalize_end_row_keeps_partial_last_row() {
+        // An end point partway through a row means that row is included.
+        assert_eq!(normalize_end_row(2, Point::new(5, 3)), 5);
+    }
+
+    #[test]
+    fn normalize_end_row_single_row_selection() {
+        assert_eq!(normalize_end_row(2, Point::new(2, 0)), 2);
+    }
+
+    #[test]
+    fn next_diagnostic_range_picks_nearest_following() {
+        let ranges = vec![2..4, 10..12, 20..25];
+        assert_eq!(next_diagnostic_range(ranges.into_iter(), 5), Some(10..12));
+    }
+
+    #[test]
+    fn next_diagnostic_range_wraps_around() {
+        let ranges = vec![2..4, 10..12, 20..25];
+        assert_eq!(next_diagnostic_range(ranges.into_iter(), 21), Some(2..4));
+    }
+
+    #[test]
+    fn prev_diagnostic_range_picks_nearest_preceding() {
+        let ranges = vec![2..4, 10..12, 20..25];
+        assert_eq!(prev_diagnostic_range(ranges.into_iter(), 15), Some(10..12));
+    }
+
+    #[test]
+    fn prev_diagnostic_range_wraps_around() {
+        let ranges = vec![2..4, 10..12, 20..25];
+        assert_eq!(prev_diagnostic_range(ranges.into_iter(), 3), Some(20..25));
+    }
+}
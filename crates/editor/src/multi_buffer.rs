@@ -0,0 +1,92 @@
+use crate::items::normalize_end_row;
+use std::fmt::Write;
+use std::path::PathBuf;
+use text::Point;
+
+pub struct ExcerptBoundary {
+    pub path: Option<PathBuf>,
+    pub range: std::ops::Range<Point>,
+    pub text: String,
+}
+
+impl MultiBufferSnapshot {
+    pub fn excerpt_boundaries(&self) -> Vec<ExcerptBoundary> {
+        self.excerpts()
+            .map(|excerpt| ExcerptBoundary {
+                path: excerpt.buffer_file().map(|file| file.full_path()),
+                range: excerpt.range().to_point(self),
+                text: excerpt.text(),
+            })
+            .collect()
+    }
+}
+
+/// Concatenates excerpts into a single document, inserting a `// path:start-end`
+/// header before every excerpt that came from a file on disk.
+pub fn render_excerpts(excerpts: &[ExcerptBoundary]) -> String {
+    let mut text = String::new();
+    for excerpt in excerpts {
+        if !text.is_empty() {
+            text.push('\n');
+        }
+        if let Some(path) = &excerpt.path {
+            let start_row = excerpt.range.start.row;
+            let end_row = normalize_end_row(start_row, excerpt.range.end);
+            writeln!(
+                text,
+                "// {}:{}-{}",
+                path.display(),
+                start_row + 1,
+                end_row + 1,
+            )
+            .unwrap();
+        }
+        text.push_str(&excerpt.text);
+        if !excerpt.text.ends_with('\n') {
+            text.push('\n');
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_excerpts_normalizes_trailing_newline_row() {
+        let excerpts = vec![ExcerptBoundary {
+            path: Some(PathBuf::from("src/lib.rs")),
+            range: Point::new(2, 4)..Point::new(5, 0),
+            text: "fn a() {}\nfn b() {}\nfn c() {}\n".to_string(),
+        }];
+
+        let text = render_excerpts(&excerpts);
+
+        assert!(
+            text.starts_with("// src/lib.rs:3-5\n"),
+            "expected header to report rows 3-5, got: {:?}",
+            text
+        );
+    }
+
+    #[test]
+    fn render_excerpts_joins_multiple_excerpts_with_blank_line() {
+        let excerpts = vec![
+            ExcerptBoundary {
+                path: Some(PathBuf::from("a.rs")),
+                range: Point::new(0, 0)..Point::new(1, 0),
+                text: "fn a() {}\n".to_string(),
+            },
+            ExcerptBoundary {
+                path: Some(PathBuf::from("b.rs")),
+                range: Point::new(4, 0)..Point::new(5, 0),
+                text: "fn b() {}\n".to_string(),
+            },
+        ];
+
+        let text = render_excerpts(&excerpts);
+
+        assert_eq!(text, "// a.rs:1-1\nfn a() {}\n\n// b.rs:5-5\nfn b() {}\n");
+    }
+}
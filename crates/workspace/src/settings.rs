@@ -0,0 +1,70 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use theme::Theme;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CursorPositionFormat {
+    #[default]
+    Minimal,
+    Detailed,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct LanguageOverrides {
+    pub format_on_save: Option<bool>,
+}
+
+#[derive(Clone)]
+pub struct Settings {
+    pub theme: Arc<Theme>,
+    pub cursor_position_format: CursorPositionFormat,
+    pub format_on_save: bool,
+    pub language_overrides: HashMap<String, LanguageOverrides>,
+}
+
+impl Settings {
+    pub fn cursor_position_detailed(&self) -> bool {
+        self.cursor_position_format == CursorPositionFormat::Detailed
+    }
+
+    pub fn format_on_save(&self, language_name: Option<&str>) -> bool {
+        language_name
+            .and_then(|name| self.language_overrides.get(name))
+            .and_then(|overrides| overrides.format_on_save)
+            .unwrap_or(self.format_on_save)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_settings() -> Settings {
+        Settings {
+            theme: Arc::new(Theme::default()),
+            cursor_position_format: CursorPositionFormat::Minimal,
+            format_on_save: true,
+            language_overrides: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn format_on_save_falls_back_to_global_default() {
+        let settings = test_settings();
+        assert!(settings.format_on_save(None));
+        assert!(settings.format_on_save(Some("Rust")));
+    }
+
+    #[test]
+    fn format_on_save_language_override_wins() {
+        let mut settings = test_settings();
+        settings.language_overrides.insert(
+            "Markdown".to_string(),
+            LanguageOverrides {
+                format_on_save: Some(false),
+            },
+        );
+        assert!(!settings.format_on_save(Some("Markdown")));
+        assert!(settings.format_on_save(Some("Rust")));
+    }
+}